@@ -1,3 +1,77 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn git(args: &[&str]) -> Option<String> {
+    Command::new("git")
+        .args(args)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|text| text.trim().to_string())
+}
+
+/// Unix time of the build. Honours `SOURCE_DATE_EPOCH` so that packagers
+/// can produce reproducible builds.
+fn build_timestamp() -> u64 {
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+    match env::var("SOURCE_DATE_EPOCH") {
+        Ok(value) => value
+            .trim()
+            .parse()
+            .expect("SOURCE_DATE_EPOCH must be a Unix timestamp in seconds"),
+        Err(_) => SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system clock is before the Unix epoch")
+            .as_secs(),
+    }
+}
+
+fn rerun_if_exists(path: &Path) {
+    // A missing path would make cargo rerun this script on every build
+    // (e.g. when building from a source tarball without `.git`).
+    if path.exists() {
+        println!("cargo:rerun-if-changed={}", path.display());
+    }
+}
+
 fn main() {
+    // Build provenance for the `get_build_info` command. The script reruns
+    // when the Rust or frontend sources change, or when HEAD moves to another
+    // commit or tag, so the dirty marker and timestamp track the code that
+    // is embedded in the binary.
+    let commit = git(&["describe", "--always", "--dirty"]).unwrap_or_else(|| "unknown".to_string());
+    let timestamp = build_timestamp();
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR is set by cargo"));
+    fs::write(
+        out_dir.join("build_info.rs"),
+        format!(
+            "pub const GIT_COMMIT: &str = {:?};\npub const BUILD_TIMESTAMP: u64 = {};\n",
+            commit, timestamp
+        ),
+    )
+    .expect("failed to write build_info.rs");
+
+    for source in ["src", "Cargo.toml", "../src", "../public", "../index.html"] {
+        rerun_if_exists(Path::new(source));
+    }
+    // Watch HEAD and the ref it points at rather than the index, which git
+    // rewrites on a plain `git status` and would force needless rebuilds.
+    let mut git_paths = vec![
+        "HEAD".to_string(),
+        "packed-refs".to_string(),
+        "refs/tags".to_string(),
+    ];
+    git_paths.extend(git(&["symbolic-ref", "-q", "HEAD"]));
+    for git_path in git_paths {
+        if let Some(path) = git(&["rev-parse", "--git-path", &git_path]) {
+            rerun_if_exists(Path::new(&path));
+        }
+    }
+
     tauri_build::build()
 }
@@ -1,14 +1,417 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{
+    AppHandle, Manager, Monitor, PhysicalPosition, PhysicalSize, RunEvent, Runtime, WebviewWindow,
+    WindowEvent,
+};
+
+mod build_info {
+    include!(concat!(env!("OUT_DIR"), "/build_info.rs"));
+}
+
+const MAIN_WINDOW_LABEL: &str = "main";
+const WINDOW_STATE_FILE: &str = "window-state.json";
+/// Height of the strip at the top of the window that must stay on a monitor
+/// for the saved position to be reused, so the title bar can still be grabbed.
+const TITLE_BAR_HEIGHT: i64 = 32;
+/// Minimum width of that strip that must be visible on a single monitor.
+const MIN_VISIBLE_TITLE_BAR_WIDTH: i64 = 100;
+
+#[derive(Serialize)]
+pub struct BuildInfo {
+    version: String,
+    git_commit: String,
+    build_timestamp: u64,
+    tauri_version: String,
+}
+
+/// A rectangle in physical pixels. For the main window this is its outer
+/// position combined with its inner size, which is what `set_size` applies.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Rect {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+}
+
+impl From<&Monitor> for Rect {
+    fn from(monitor: &Monitor) -> Self {
+        Rect {
+            x: monitor.position().x,
+            y: monitor.position().y,
+            width: monitor.size().width,
+            height: monitor.size().height,
+        }
+    }
+}
+
+/// Last normal (neither maximized nor minimized) bounds of the main window,
+/// updated as it is moved or resized so they survive a later maximize.
+#[derive(Default)]
+struct NormalBounds(Mutex<Option<Rect>>);
+
+impl NormalBounds {
+    fn get(&self) -> Option<Rect> {
+        self.0.lock().ok().and_then(|bounds| *bounds)
+    }
+
+    fn set(&self, rect: Rect) {
+        if let Ok(mut bounds) = self.0.lock() {
+            *bounds = Some(rect);
+        }
+    }
+}
+
+/// Geometry of the main window. `x`/`y`/`width`/`height` always describe the
+/// normal (non-maximized) bounds; a zero size means none has been recorded yet.
+#[derive(Serialize, Deserialize)]
+pub struct WindowState {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+    maximized: bool,
+}
+
+impl WindowState {
+    fn bounds(&self) -> Option<Rect> {
+        (self.width > 0 && self.height > 0).then_some(Rect {
+            x: self.x,
+            y: self.y,
+            width: self.width,
+            height: self.height,
+        })
+    }
+}
+
+fn window_state_path<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    Ok(dir.join(WINDOW_STATE_FILE))
+}
+
+fn write_window_state<R: Runtime>(app: &AppHandle<R>, state: &WindowState) -> Result<(), String> {
+    let path = window_state_path(app)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(state).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| e.to_string())
+}
+
+fn read_window_state<R: Runtime>(app: &AppHandle<R>) -> Option<WindowState> {
+    let json = fs::read_to_string(window_state_path(app).ok()?).ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+/// The window's current bounds, or `None` while it is maximized or minimized
+/// and its reported geometry is not the user's normal size.
+fn current_normal_bounds<R: Runtime>(window: &WebviewWindow<R>) -> Option<Rect> {
+    if window.is_maximized().unwrap_or(true) || window.is_minimized().unwrap_or(true) {
+        return None;
+    }
+    let position = window.outer_position().ok()?;
+    let size = window.inner_size().ok()?;
+    Some(Rect {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+    })
+}
+
+fn record_normal_bounds<R: Runtime>(app: &AppHandle<R>) {
+    if let Some(bounds) = app
+        .get_webview_window(MAIN_WINDOW_LABEL)
+        .as_ref()
+        .and_then(current_normal_bounds)
+    {
+        app.state::<NormalBounds>().set(bounds);
+    }
+}
+
+/// Captures the main window's geometry and writes it to disk. While the
+/// window is maximized or minimized, the last normal bounds recorded from
+/// move/resize events are saved instead, falling back to the saved file.
+fn save_main_window_state<R: Runtime>(app: &AppHandle<R>) -> Result<WindowState, String> {
+    let window = app
+        .get_webview_window(MAIN_WINDOW_LABEL)
+        .ok_or_else(|| "Main window not found".to_string())?;
+    let maximized = window.is_maximized().map_err(|e| e.to_string())?;
+    let bounds = current_normal_bounds(&window)
+        .or_else(|| app.state::<NormalBounds>().get())
+        .or_else(|| read_window_state(app).and_then(|state| state.bounds()));
+
+    let state = match bounds {
+        Some(bounds) => WindowState {
+            x: bounds.x,
+            y: bounds.y,
+            width: bounds.width,
+            height: bounds.height,
+            maximized,
+        },
+        None => WindowState {
+            x: 0,
+            y: 0,
+            width: 0,
+            height: 0,
+            maximized,
+        },
+    };
+
+    write_window_state(app, &state)?;
+    Ok(state)
+}
+
+/// The monitor holding the largest part of the window's title bar, provided
+/// enough of it is visible there for the user to grab and move the window.
+fn title_bar_monitor(window: &Rect, monitors: &[Rect]) -> Option<Rect> {
+    let left = i64::from(window.x);
+    let top = i64::from(window.y);
+    let right = left + i64::from(window.width);
+    let bottom = top + TITLE_BAR_HEIGHT;
+    let min_width = MIN_VISIBLE_TITLE_BAR_WIDTH.min(i64::from(window.width));
+
+    monitors
+        .iter()
+        .filter_map(|monitor| {
+            let monitor_left = i64::from(monitor.x);
+            let monitor_top = i64::from(monitor.y);
+            let monitor_right = monitor_left + i64::from(monitor.width);
+            let monitor_bottom = monitor_top + i64::from(monitor.height);
+
+            let visible_width = right.min(monitor_right) - left.max(monitor_left);
+            let visible_height = bottom.min(monitor_bottom) - top.max(monitor_top);
+            (visible_width >= min_width && visible_height >= TITLE_BAR_HEIGHT)
+                .then_some((visible_width, *monitor))
+        })
+        .max_by_key(|(visible_width, _)| *visible_width)
+        .map(|(_, monitor)| monitor)
+}
+
+/// Shrinks the window to fit on `monitor`, keeping its position.
+fn clamp_to_monitor(window: Rect, monitor: &Rect) -> Rect {
+    Rect {
+        width: window.width.min(monitor.width),
+        height: window.height.min(monitor.height),
+        ..window
+    }
+}
+
+/// Fits the window on `monitor` and centers it there.
+fn center_on_monitor(window: Rect, monitor: &Rect) -> Rect {
+    let clamped = clamp_to_monitor(window, monitor);
+    Rect {
+        x: monitor.x + ((monitor.width - clamped.width) / 2) as i32,
+        y: monitor.y + ((monitor.height - clamped.height) / 2) as i32,
+        ..clamped
+    }
+}
+
+/// Restores the saved geometry onto the main window. A position whose title
+/// bar is no longer on any connected monitor (e.g. an unplugged display) is
+/// dropped and the window is centered on the primary monitor instead. The
+/// size is always clamped to the monitor the window ends up on.
+fn restore_window_state<R: Runtime>(app: &AppHandle<R>) {
+    let (Some(window), Some(state)) = (
+        app.get_webview_window(MAIN_WINDOW_LABEL),
+        read_window_state(app),
+    ) else {
+        return;
+    };
+
+    if let Some(saved) = state.bounds() {
+        let monitors: Vec<Rect> = window
+            .available_monitors()
+            .unwrap_or_default()
+            .iter()
+            .map(Rect::from)
+            .collect();
+        let primary = window.primary_monitor().ok().flatten();
+        let bounds = match (title_bar_monitor(&saved, &monitors), primary) {
+            (Some(monitor), _) => clamp_to_monitor(saved, &monitor),
+            (None, Some(primary)) => center_on_monitor(saved, &Rect::from(&primary)),
+            (None, None) => saved,
+        };
+
+        let _ = window.set_size(PhysicalSize::new(bounds.width, bounds.height));
+        let _ = window.set_position(PhysicalPosition::new(bounds.x, bounds.y));
+        app.state::<NormalBounds>().set(bounds);
+    }
+
+    if state.maximized {
+        let _ = window.maximize();
+    }
+}
+
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 #[tauri::command]
 fn greet(name: &str) -> String {
     format!("Hello, {}! You've been greeted from Rust!", name)
 }
 
+#[tauri::command]
+fn get_build_info() -> BuildInfo {
+    BuildInfo {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        git_commit: build_info::GIT_COMMIT.to_string(),
+        build_timestamp: build_info::BUILD_TIMESTAMP,
+        tauri_version: tauri::VERSION.to_string(),
+    }
+}
+
+#[tauri::command]
+fn save_window_state(app: AppHandle) -> Result<WindowState, String> {
+    save_main_window_state(&app)
+}
+
+#[tauri::command]
+fn load_window_state(app: AppHandle) -> Option<WindowState> {
+    read_window_state(&app)
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
-        .invoke_handler(tauri::generate_handler![greet])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .manage(NormalBounds::default())
+        .setup(|app| {
+            restore_window_state(app.handle());
+            if let Some(window) = app.get_webview_window(MAIN_WINDOW_LABEL) {
+                window.show()?;
+            }
+            Ok(())
+        })
+        .on_window_event(|window, event| {
+            if window.label() != MAIN_WINDOW_LABEL {
+                return;
+            }
+            match event {
+                WindowEvent::Moved(_) | WindowEvent::Resized(_) => {
+                    record_normal_bounds(window.app_handle());
+                }
+                WindowEvent::CloseRequested { .. } => {
+                    let _ = save_main_window_state(window.app_handle());
+                }
+                _ => {}
+            }
+        })
+        .invoke_handler(tauri::generate_handler![
+            greet,
+            get_build_info,
+            save_window_state,
+            load_window_state
+        ])
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            // Quitting from the app menu (Cmd+Q on macOS) exits without a
+            // CloseRequested event, so save the geometry here as well.
+            if let RunEvent::ExitRequested { .. } = event {
+                let _ = save_main_window_state(app);
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: i32, y: i32, width: u32, height: u32) -> Rect {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn title_bar_fully_on_screen() {
+        let monitor = rect(0, 0, 1920, 1080);
+        let window = rect(100, 100, 800, 600);
+        assert_eq!(title_bar_monitor(&window, &[monitor]), Some(monitor));
+    }
+
+    #[test]
+    fn title_bar_on_unplugged_monitor() {
+        // Saved on a second display to the right that is no longer attached.
+        let monitors = [rect(0, 0, 1920, 1080)];
+        assert_eq!(
+            title_bar_monitor(&rect(2200, 100, 800, 600), &monitors),
+            None
+        );
+        // Only the bottom few pixels of the title bar reach the monitor.
+        assert_eq!(
+            title_bar_monitor(&rect(100, -20, 800, 600), &monitors),
+            None
+        );
+        // The top-left corner is on screen but almost nothing else is.
+        assert_eq!(
+            title_bar_monitor(&rect(1910, 1070, 800, 600), &monitors),
+            None
+        );
+    }
+
+    #[test]
+    fn title_bar_straddling_two_monitors() {
+        let left = rect(0, 0, 1920, 1080);
+        let right = rect(1920, 0, 1920, 1080);
+        // Mostly on the right monitor.
+        assert_eq!(
+            title_bar_monitor(&rect(1800, 100, 800, 600), &[left, right]),
+            Some(right)
+        );
+        // Enough is left on the first monitor after it is unplugged.
+        assert_eq!(
+            title_bar_monitor(&rect(1800, 100, 800, 600), &[left]),
+            Some(left)
+        );
+        // Too little on either monitor on its own.
+        assert_eq!(
+            title_bar_monitor(&rect(1870, 100, 100, 600), &[left, right]),
+            None
+        );
+    }
+
+    #[test]
+    fn title_bar_narrower_than_minimum() {
+        let monitor = rect(0, 0, 1920, 1080);
+        assert_eq!(
+            title_bar_monitor(&rect(100, 100, 60, 400), &[monitor]),
+            Some(monitor)
+        );
+        assert_eq!(
+            title_bar_monitor(&rect(1890, 100, 60, 400), &[monitor]),
+            None
+        );
+    }
+
+    #[test]
+    fn title_bar_on_monitor_with_negative_origin() {
+        let primary = rect(0, 0, 1920, 1080);
+        let left = rect(-1920, -200, 1920, 1080);
+        assert_eq!(
+            title_bar_monitor(&rect(-1500, -100, 800, 600), &[primary, left]),
+            Some(left)
+        );
+        assert_eq!(
+            title_bar_monitor(&rect(-2500, 0, 800, 600), &[primary, left]),
+            Some(left)
+        );
+        assert_eq!(
+            title_bar_monitor(&rect(-2700, 0, 800, 600), &[primary, left]),
+            None
+        );
+    }
+
+    #[test]
+    fn oversized_window_is_clamped_and_centered() {
+        let monitor = rect(0, 0, 1366, 768);
+        let saved = rect(3000, 50, 2400, 600);
+        assert_eq!(clamp_to_monitor(saved, &monitor), rect(3000, 50, 1366, 600));
+        assert_eq!(center_on_monitor(saved, &monitor), rect(0, 84, 1366, 600));
+    }
 }